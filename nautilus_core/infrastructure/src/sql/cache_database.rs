@@ -0,0 +1,322 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use std::collections::HashMap;
+
+use nautilus_model::{
+    identifiers::instrument_id::InstrumentId, instruments::any::InstrumentAny,
+    types::currency::Currency,
+};
+use sqlx::{PgPool, Row};
+use tokio::{
+    sync::{mpsc, oneshot},
+    task::JoinHandle,
+};
+
+use crate::sql::{
+    pg::{connect_pg, migrate, PostgresConnectOptions},
+    serialization::SerializationFormat,
+};
+
+/// A mutation queued for background persistence by a [`PostgresCacheDatabase`].
+///
+/// Typed values carry their already-encoded `definition` bytes and the [`SerializationFormat`]
+/// they were encoded with, so the tag lands in the row and reads decode correctly regardless of
+/// which writer produced them.
+#[derive(Debug)]
+pub enum DatabaseQuery {
+    Add(String, Vec<u8>),
+    AddCurrency {
+        code: String,
+        format: SerializationFormat,
+        definition: Vec<u8>,
+    },
+    AddInstrument {
+        id: String,
+        format: SerializationFormat,
+        definition: Vec<u8>,
+    },
+    /// A barrier: once processed, every mutation queued before it has been committed. The attached
+    /// channel resolves with any error observed during that background persistence.
+    Flush(oneshot::Sender<anyhow::Result<()>>),
+}
+
+/// Backend-agnostic cache persistence operations.
+///
+/// Implementors decide whether writes are synchronous (as in [`super::sqlite::SqliteCacheDatabase`])
+/// or dispatched to a background task (as in [`PostgresCacheDatabase`]); callers select a backend
+/// by config and program against this trait.
+#[async_trait::async_trait]
+pub trait CacheDatabase {
+    /// Persists a general object blob under `key`.
+    async fn add(&self, key: String, value: Vec<u8>) -> anyhow::Result<()>;
+    /// Loads every general object blob as a map keyed by id.
+    async fn load(&self) -> anyhow::Result<HashMap<String, Vec<u8>>>;
+    /// Persists a currency definition.
+    async fn add_currency(&self, currency: Currency) -> anyhow::Result<()>;
+    /// Loads a single currency by its ISO/`code`, if present.
+    async fn load_currency(&self, code: &str) -> anyhow::Result<Option<Currency>>;
+    /// Loads every currency ordered by `code`.
+    async fn load_currencies(&self) -> anyhow::Result<Vec<Currency>>;
+    /// Persists an instrument definition.
+    async fn add_instrument(&self, instrument: InstrumentAny) -> anyhow::Result<()>;
+    /// Loads a single instrument by its [`InstrumentId`], if present.
+    async fn load_instrument(
+        &self,
+        instrument_id: InstrumentId,
+    ) -> anyhow::Result<Option<InstrumentAny>>;
+    /// Loads every instrument ordered by id.
+    async fn load_instruments(&self) -> anyhow::Result<Vec<InstrumentAny>>;
+    /// Blocks until every queued write has been durably persisted, surfacing any background error.
+    ///
+    /// Synchronous backends persist inline and leave this a no-op; backends that dispatch writes
+    /// asynchronously (such as [`PostgresCacheDatabase`]) override it.
+    async fn flush(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+/// A [`CacheDatabase`] backed by a shared Postgres server.
+///
+/// Reads hit the pool directly; writes are dispatched over a channel to a background task so hot
+/// paths never block on the database round-trip.
+#[derive(Debug)]
+pub struct PostgresCacheDatabase {
+    pub pool: PgPool,
+    format: SerializationFormat,
+    tx: mpsc::UnboundedSender<DatabaseQuery>,
+    handle: JoinHandle<()>,
+}
+
+impl PostgresCacheDatabase {
+    /// Connects to Postgres (falling back to localhost defaults for any `None` field) and spawns
+    /// the background write handler.
+    ///
+    /// `serialization` selects the wire format used to encode stored currencies and instruments,
+    /// defaulting to [`SerializationFormat::Json`]; the format is recorded per row so previously
+    /// written rows remain readable after the setting changes.
+    pub async fn connect(
+        host: Option<String>,
+        port: Option<u16>,
+        username: Option<String>,
+        password: Option<String>,
+        database: Option<String>,
+        serialization: Option<SerializationFormat>,
+    ) -> anyhow::Result<Self> {
+        let format = serialization.unwrap_or_default();
+        let options = PostgresConnectOptions::new(
+            host.unwrap_or_else(|| "localhost".to_string()),
+            port.unwrap_or(5432),
+            username.unwrap_or_else(|| "postgres".to_string()),
+            password.unwrap_or_else(|| "pass".to_string()),
+            database.unwrap_or_else(|| "nautilus".to_string()),
+        );
+        let pool = connect_pg(options.into()).await?;
+        migrate(&pool).await?;
+
+        let (tx, rx) = mpsc::unbounded_channel::<DatabaseQuery>();
+        let handle = tokio::spawn(Self::handle_queries(pool.clone(), rx));
+        Ok(Self {
+            pool,
+            format,
+            tx,
+            handle,
+        })
+    }
+
+    /// Drains queued mutations, executing each against `pool` in arrival order.
+    ///
+    /// Persistence errors are logged and retained so the next [`DatabaseQuery::Flush`] can surface
+    /// them to the caller; the error is cleared once reported.
+    async fn handle_queries(pool: PgPool, mut rx: mpsc::UnboundedReceiver<DatabaseQuery>) {
+        let mut last_error: Option<anyhow::Error> = None;
+        while let Some(query) = rx.recv().await {
+            if let DatabaseQuery::Flush(tx) = query {
+                let _ = tx.send(last_error.take().map_or(Ok(()), Err));
+                continue;
+            }
+            if let Err(e) = Self::execute(&pool, query).await {
+                log::error!("Error persisting cache mutation: {e}");
+                last_error.get_or_insert(e);
+            }
+        }
+    }
+
+    /// Persists a general object blob and waits for it to land (see [`Self::flush`]).
+    pub async fn add_and_wait(&self, key: String, value: Vec<u8>) -> anyhow::Result<()> {
+        self.add(key, value).await?;
+        self.flush().await
+    }
+
+    /// Persists a currency and waits for it to land (see [`Self::flush`]).
+    pub async fn add_currency_and_wait(&self, currency: Currency) -> anyhow::Result<()> {
+        self.add_currency(currency).await?;
+        self.flush().await
+    }
+
+    /// Persists an instrument and waits for it to land (see [`Self::flush`]).
+    pub async fn add_instrument_and_wait(&self, instrument: InstrumentAny) -> anyhow::Result<()> {
+        self.add_instrument(instrument).await?;
+        self.flush().await
+    }
+
+    async fn execute(pool: &PgPool, query: DatabaseQuery) -> anyhow::Result<()> {
+        match query {
+            DatabaseQuery::Add(key, value) => {
+                sqlx::query(
+                    "INSERT INTO general (id, value) VALUES ($1, $2) \
+                     ON CONFLICT (id) DO UPDATE SET value = $2",
+                )
+                .bind(key)
+                .bind(value)
+                .execute(pool)
+                .await?;
+            }
+            DatabaseQuery::AddCurrency {
+                code,
+                format,
+                definition,
+            } => {
+                sqlx::query(
+                    "INSERT INTO currency (code, format, definition) VALUES ($1, $2, $3) \
+                     ON CONFLICT (code) DO UPDATE SET format = $2, definition = $3",
+                )
+                .bind(code)
+                .bind(format.as_str())
+                .bind(definition)
+                .execute(pool)
+                .await?;
+            }
+            DatabaseQuery::AddInstrument {
+                id,
+                format,
+                definition,
+            } => {
+                sqlx::query(
+                    "INSERT INTO instrument (id, format, definition) VALUES ($1, $2, $3) \
+                     ON CONFLICT (id) DO UPDATE SET format = $2, definition = $3",
+                )
+                .bind(id)
+                .bind(format.as_str())
+                .bind(definition)
+                .execute(pool)
+                .await?;
+            }
+            DatabaseQuery::Flush(_) => unreachable!("flush is handled by the query loop"),
+        }
+        Ok(())
+    }
+}
+
+impl Drop for PostgresCacheDatabase {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+#[async_trait::async_trait]
+impl CacheDatabase for PostgresCacheDatabase {
+    async fn add(&self, key: String, value: Vec<u8>) -> anyhow::Result<()> {
+        self.tx.send(DatabaseQuery::Add(key, value))?;
+        Ok(())
+    }
+
+    async fn load(&self) -> anyhow::Result<HashMap<String, Vec<u8>>> {
+        let rows = sqlx::query("SELECT id, value FROM general")
+            .fetch_all(&self.pool)
+            .await?;
+        let mut map = HashMap::new();
+        for row in rows {
+            map.insert(row.try_get::<String, _>("id")?, row.try_get::<Vec<u8>, _>("value")?);
+        }
+        Ok(map)
+    }
+
+    async fn add_currency(&self, currency: Currency) -> anyhow::Result<()> {
+        self.tx.send(DatabaseQuery::AddCurrency {
+            code: currency.code.to_string(),
+            format: self.format,
+            definition: self.format.encode(&currency)?,
+        })?;
+        Ok(())
+    }
+
+    async fn load_currency(&self, code: &str) -> anyhow::Result<Option<Currency>> {
+        let row = sqlx::query("SELECT format, definition FROM currency WHERE code = $1")
+            .bind(code)
+            .fetch_optional(&self.pool)
+            .await?;
+        match row {
+            Some(row) => Ok(Some(decode_row(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn load_currencies(&self) -> anyhow::Result<Vec<Currency>> {
+        let rows = sqlx::query("SELECT format, definition FROM currency ORDER BY code")
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(decode_row).collect()
+    }
+
+    async fn add_instrument(&self, instrument: InstrumentAny) -> anyhow::Result<()> {
+        self.tx.send(DatabaseQuery::AddInstrument {
+            id: instrument.id().to_string(),
+            format: self.format,
+            definition: self.format.encode(&instrument)?,
+        })?;
+        Ok(())
+    }
+
+    async fn load_instrument(
+        &self,
+        instrument_id: InstrumentId,
+    ) -> anyhow::Result<Option<InstrumentAny>> {
+        let row = sqlx::query("SELECT format, definition FROM instrument WHERE id = $1")
+            .bind(instrument_id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+        match row {
+            Some(row) => Ok(Some(decode_row(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn load_instruments(&self) -> anyhow::Result<Vec<InstrumentAny>> {
+        let rows = sqlx::query("SELECT format, definition FROM instrument ORDER BY id")
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(decode_row).collect()
+    }
+
+    /// Drains the pending write queue, resolving only once every buffered insert has been
+    /// committed. Returns the first error observed during background persistence since the last
+    /// flush, if any.
+    ///
+    /// This replaces timing-based waits: callers needing durability at a checkpoint can `await`
+    /// this before proceeding instead of sleeping.
+    async fn flush(&self) -> anyhow::Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.tx.send(DatabaseQuery::Flush(tx))?;
+        rx.await?
+    }
+}
+
+/// Decodes a `(format, definition)` row using the format tag stored alongside the bytes, so rows
+/// written with different formats all read back correctly.
+fn decode_row<T: serde::de::DeserializeOwned>(row: &sqlx::postgres::PgRow) -> anyhow::Result<T> {
+    let format = SerializationFormat::from_tag(row.try_get("format")?)?;
+    format.decode(&row.try_get::<Vec<u8>, _>("definition")?)
+}
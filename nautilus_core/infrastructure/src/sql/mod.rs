@@ -0,0 +1,27 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! SQL-backed cache persistence.
+//!
+//! The [`cache_database::CacheDatabase`] trait abstracts over the concrete storage engine so a
+//! caller can pick a backend by config: [`cache_database::PostgresCacheDatabase`] for a shared
+//! server deployment, or [`sqlite::SqliteCacheDatabase`] for an embedded, server-less
+//! store that runs anywhere (including CI and cross-platform test runs).
+
+pub mod cache_database;
+pub mod overlay;
+pub mod pg;
+pub mod serialization;
+pub mod sqlite;
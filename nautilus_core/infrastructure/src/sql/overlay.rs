@@ -0,0 +1,239 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+};
+
+use nautilus_model::{
+    identifiers::instrument_id::InstrumentId, instruments::any::InstrumentAny,
+    types::currency::Currency,
+};
+
+use crate::sql::cache_database::CacheDatabase;
+
+/// Buffered mutations awaiting a [`DatabaseOverlay::commit`].
+#[derive(Clone, Debug, Default)]
+struct Buffer {
+    general: HashMap<String, Vec<u8>>,
+    currencies: HashMap<String, Currency>,
+    instruments: HashMap<InstrumentId, InstrumentAny>,
+}
+
+/// A copy-on-write wrapper composing any [`CacheDatabase`].
+///
+/// Writes accumulate in an in-memory layer and never touch the backing store until
+/// [`DatabaseOverlay::commit`]; reads consult the overlay first and fall through to the underlying
+/// database on a miss. This lets a backtest or dry-run mutate instruments and currencies against
+/// the exact production cache state and then either persist with `commit` or throw the work away
+/// with [`DatabaseOverlay::discard`] — without holding a transaction open for the whole run.
+#[derive(Debug)]
+pub struct DatabaseOverlay<D: CacheDatabase> {
+    base: D,
+    buffer: Mutex<Buffer>,
+}
+
+impl<D: CacheDatabase> DatabaseOverlay<D> {
+    /// Wraps `base` in a fresh, empty overlay.
+    #[must_use]
+    pub fn new(base: D) -> Self {
+        Self {
+            base,
+            buffer: Mutex::new(Buffer::default()),
+        }
+    }
+
+    /// Flushes every buffered mutation down to the backing store and clears it from the overlay.
+    ///
+    /// Writes are dispatched, then [`CacheDatabase::flush`] blocks until the backing store has
+    /// acknowledged persistence — this matters for asynchronous backends like
+    /// [`super::cache_database::PostgresCacheDatabase`], where `add_*` only enqueues and returns
+    /// before the write lands. Only the committed keys are then removed, so any mutation that
+    /// arrived on another task during the awaits stays buffered rather than being dropped. If a
+    /// write errors part-way nothing is cleared, and the idempotent upserts make a retry safe.
+    pub async fn commit(&self) -> anyhow::Result<()> {
+        let Buffer {
+            general,
+            currencies,
+            instruments,
+        } = self.buffer.lock().unwrap().clone();
+        for (key, value) in &general {
+            self.base.add(key.clone(), value.clone()).await?;
+        }
+        for currency in currencies.values() {
+            self.base.add_currency(*currency).await?;
+        }
+        for instrument in instruments.values() {
+            self.base.add_instrument(instrument.clone()).await?;
+        }
+        // Wait for the backing store to acknowledge persistence before forgetting the mutations.
+        self.base.flush().await?;
+        let mut buffer = self.buffer.lock().unwrap();
+        for key in general.keys() {
+            buffer.general.remove(key);
+        }
+        for code in currencies.keys() {
+            buffer.currencies.remove(code);
+        }
+        for id in instruments.keys() {
+            buffer.instruments.remove(id);
+        }
+        Ok(())
+    }
+
+    /// Throws the buffered mutations away, leaving the backing store untouched.
+    pub fn discard(&self) {
+        *self.buffer.lock().unwrap() = Buffer::default();
+    }
+}
+
+#[async_trait::async_trait]
+impl<D: CacheDatabase + Send + Sync> CacheDatabase for DatabaseOverlay<D> {
+    async fn add(&self, key: String, value: Vec<u8>) -> anyhow::Result<()> {
+        self.buffer.lock().unwrap().general.insert(key, value);
+        Ok(())
+    }
+
+    async fn load(&self) -> anyhow::Result<HashMap<String, Vec<u8>>> {
+        let mut map = self.base.load().await?;
+        map.extend(
+            self.buffer
+                .lock()
+                .unwrap()
+                .general
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone())),
+        );
+        Ok(map)
+    }
+
+    async fn add_currency(&self, currency: Currency) -> anyhow::Result<()> {
+        self.buffer
+            .lock()
+            .unwrap()
+            .currencies
+            .insert(currency.code.to_string(), currency);
+        Ok(())
+    }
+
+    async fn load_currency(&self, code: &str) -> anyhow::Result<Option<Currency>> {
+        if let Some(currency) = self.buffer.lock().unwrap().currencies.get(code).copied() {
+            return Ok(Some(currency));
+        }
+        self.base.load_currency(code).await
+    }
+
+    async fn load_currencies(&self) -> anyhow::Result<Vec<Currency>> {
+        let mut merged: HashMap<String, Currency> = self
+            .base
+            .load_currencies()
+            .await?
+            .into_iter()
+            .map(|c| (c.code.to_string(), c))
+            .collect();
+        merged.extend(
+            self.buffer
+                .lock()
+                .unwrap()
+                .currencies
+                .iter()
+                .map(|(k, v)| (k.clone(), *v)),
+        );
+        let mut currencies: Vec<Currency> = merged.into_values().collect();
+        currencies.sort_by(|a, b| a.code.cmp(&b.code));
+        Ok(currencies)
+    }
+
+    async fn add_instrument(&self, instrument: InstrumentAny) -> anyhow::Result<()> {
+        self.buffer
+            .lock()
+            .unwrap()
+            .instruments
+            .insert(instrument.id(), instrument);
+        Ok(())
+    }
+
+    async fn load_instrument(
+        &self,
+        instrument_id: InstrumentId,
+    ) -> anyhow::Result<Option<InstrumentAny>> {
+        if let Some(instrument) = self.buffer.lock().unwrap().instruments.get(&instrument_id).cloned()
+        {
+            return Ok(Some(instrument));
+        }
+        self.base.load_instrument(instrument_id).await
+    }
+
+    async fn load_instruments(&self) -> anyhow::Result<Vec<InstrumentAny>> {
+        let mut merged: HashMap<InstrumentId, InstrumentAny> = self
+            .base
+            .load_instruments()
+            .await?
+            .into_iter()
+            .map(|i| (i.id(), i))
+            .collect();
+        merged.extend(
+            self.buffer
+                .lock()
+                .unwrap()
+                .instruments
+                .iter()
+                .map(|(k, v)| (*k, v.clone())),
+        );
+        let mut instruments: Vec<InstrumentAny> = merged.into_values().collect();
+        instruments.sort_by_key(InstrumentAny::id);
+        Ok(instruments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nautilus_model::instruments::{any::InstrumentAny, stubs::currency_pair_ethusdt};
+
+    use super::*;
+    use crate::sql::sqlite::SqliteCacheDatabase;
+
+    #[tokio::test]
+    async fn test_overlaid_instrument_visible_via_overlay_but_absent_from_base_until_commit() {
+        let base = SqliteCacheDatabase::connect(None).await.unwrap();
+        let instrument = InstrumentAny::CurrencyPair(currency_pair_ethusdt());
+        let id = instrument.id();
+
+        let overlay = DatabaseOverlay::new(base.clone());
+        overlay.add_instrument(instrument.clone()).await.unwrap();
+
+        // Visible through the overlay, still absent from the backing store.
+        assert_eq!(overlay.load_instrument(id).await.unwrap(), Some(instrument.clone()));
+        assert_eq!(base.load_instrument(id).await.unwrap(), None);
+
+        overlay.commit().await.unwrap();
+        assert_eq!(base.load_instrument(id).await.unwrap(), Some(instrument));
+    }
+
+    #[tokio::test]
+    async fn test_discard_drops_buffered_mutations() {
+        let base = SqliteCacheDatabase::connect(None).await.unwrap();
+        let instrument = InstrumentAny::CurrencyPair(currency_pair_ethusdt());
+        let id = instrument.id();
+
+        let overlay = DatabaseOverlay::new(base.clone());
+        overlay.add_instrument(instrument).await.unwrap();
+        overlay.discard();
+
+        assert_eq!(overlay.load_instrument(id).await.unwrap(), None);
+        assert_eq!(base.load_instrument(id).await.unwrap(), None);
+    }
+}
@@ -0,0 +1,155 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use sqlx::{postgres::PgConnectOptions, PgPool, Row};
+
+/// Connection parameters for a Postgres-backed cache.
+#[derive(Clone, Debug)]
+pub struct PostgresConnectOptions {
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub password: String,
+    pub database: String,
+}
+
+impl PostgresConnectOptions {
+    /// Creates a new [`PostgresConnectOptions`] instance.
+    #[must_use]
+    pub fn new(
+        host: String,
+        port: u16,
+        username: String,
+        password: String,
+        database: String,
+    ) -> Self {
+        Self {
+            host,
+            port,
+            username,
+            password,
+            database,
+        }
+    }
+}
+
+impl From<PostgresConnectOptions> for PgConnectOptions {
+    fn from(options: PostgresConnectOptions) -> Self {
+        PgConnectOptions::new()
+            .host(&options.host)
+            .port(options.port)
+            .username(&options.username)
+            .password(&options.password)
+            .database(&options.database)
+    }
+}
+
+/// Opens a connection pool against the Postgres server described by `options`.
+pub async fn connect_pg(options: PgConnectOptions) -> anyhow::Result<PgPool> {
+    Ok(PgPool::connect_with(options).await?)
+}
+
+/// Ordered, embedded up-migrations applied by [`migrate`].
+///
+/// Each entry is `(version, sql)` and must be append-only: never edit or reorder a shipped
+/// migration, only add a higher version at the end. Steps create or evolve schema without
+/// discarding stored `general` blobs, currency rows or instrument rows.
+const MIGRATIONS: &[(i64, &str)] = &[
+    (
+        1,
+        r"
+        CREATE TABLE IF NOT EXISTS general (
+            id    TEXT  PRIMARY KEY,
+            value BYTEA NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS currency (
+            code       TEXT  PRIMARY KEY,
+            definition JSONB NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS instrument (
+            id         TEXT  PRIMARY KEY,
+            definition JSONB NOT NULL
+        );
+        ",
+    ),
+    // Store definitions as opaque encoded bytes tagged with their serialization format so JSON and
+    // MessagePack rows coexist. Existing JSONB rows migrate to their UTF-8 bytes and stay decodable
+    // as JSON (the default for their back-filled `format`).
+    (
+        2,
+        r"
+        ALTER TABLE currency ADD COLUMN format TEXT NOT NULL DEFAULT 'json';
+        ALTER TABLE currency ALTER COLUMN definition TYPE BYTEA
+            USING convert_to(definition::text, 'UTF8');
+        ALTER TABLE instrument ADD COLUMN format TEXT NOT NULL DEFAULT 'json';
+        ALTER TABLE instrument ALTER COLUMN definition TYPE BYTEA
+            USING convert_to(definition::text, 'UTF8');
+        ",
+    ),
+];
+
+/// Returns the highest applied migration version, or `0` on a database that has never been
+/// migrated.
+async fn current_version(pool: &PgPool) -> anyhow::Result<i64> {
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (version BIGINT PRIMARY KEY)",
+    )
+    .execute(pool)
+    .await?;
+    let row = sqlx::query("SELECT COALESCE(MAX(version), 0) AS version FROM schema_migrations")
+        .fetch_one(pool)
+        .await?;
+    Ok(row.try_get("version")?)
+}
+
+/// Brings the database up to the latest schema version, applying only the missing steps.
+///
+/// Detects the current version from `schema_migrations` and runs each higher [`MIGRATIONS`] entry
+/// in order inside a single transaction, recording its version on success. Idempotent: calling it
+/// on an up-to-date database is a no-op and existing data is preserved across upgrades.
+pub async fn migrate(pool: &PgPool) -> anyhow::Result<()> {
+    let current = current_version(pool).await?;
+    let mut tx = pool.begin().await?;
+    for (version, sql) in MIGRATIONS {
+        if *version <= current {
+            continue;
+        }
+        // `raw_sql` so the multi-statement DDL runs on the simple query protocol; `query()` would
+        // force the prepared protocol, which rejects multiple commands in one string.
+        sqlx::raw_sql(sql).execute(&mut *tx).await?;
+        sqlx::query("INSERT INTO schema_migrations (version) VALUES ($1)")
+            .bind(version)
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Drops every Nautilus table, including `schema_migrations`. Used only by tests, to reset the
+/// database before a run.
+pub async fn delete_nautilus_postgres_tables(pool: &PgPool) -> anyhow::Result<()> {
+    sqlx::raw_sql(
+        r"
+        DROP TABLE IF EXISTS instrument;
+        DROP TABLE IF EXISTS currency;
+        DROP TABLE IF EXISTS general;
+        DROP TABLE IF EXISTS schema_migrations;
+        ",
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
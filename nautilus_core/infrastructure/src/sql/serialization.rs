@@ -0,0 +1,64 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Wire format used to encode stored currency and instrument definitions.
+///
+/// The chosen format is recorded in each row's `format` column so [`decode`](Self::decode) can
+/// read back a value regardless of which writer produced it; rows written with different formats
+/// therefore coexist in the same table.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum SerializationFormat {
+    #[default]
+    Json,
+    MsgPack,
+}
+
+impl SerializationFormat {
+    /// Returns the tag persisted alongside the data.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::MsgPack => "msgpack",
+        }
+    }
+
+    /// Parses a persisted `format` tag.
+    pub fn from_tag(tag: &str) -> anyhow::Result<Self> {
+        match tag {
+            "json" => Ok(Self::Json),
+            "msgpack" => Ok(Self::MsgPack),
+            other => anyhow::bail!("Unknown serialization format '{other}'"),
+        }
+    }
+
+    /// Encodes a typed `value` into its on-wire bytes.
+    pub fn encode<T: Serialize>(self, value: &T) -> anyhow::Result<Vec<u8>> {
+        match self {
+            Self::Json => Ok(serde_json::to_vec(value)?),
+            Self::MsgPack => Ok(rmp_serde::to_vec(value)?),
+        }
+    }
+
+    /// Decodes a typed value from `bytes` using this format.
+    pub fn decode<T: DeserializeOwned>(self, bytes: &[u8]) -> anyhow::Result<T> {
+        match self {
+            Self::Json => Ok(serde_json::from_slice(bytes)?),
+            Self::MsgPack => Ok(rmp_serde::from_slice(bytes)?),
+        }
+    }
+}
@@ -0,0 +1,198 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use std::collections::HashMap;
+
+use nautilus_model::{
+    identifiers::instrument_id::InstrumentId, instruments::any::InstrumentAny,
+    types::currency::Currency,
+};
+use sqlx::{sqlite::SqliteConnectOptions, Row, SqlitePool};
+
+use crate::sql::cache_database::CacheDatabase;
+
+/// A [`CacheDatabase`] backed by an embedded SQLite store.
+///
+/// Unlike [`super::cache_database::PostgresCacheDatabase`] this needs no database server: point it
+/// at a file (shared safely between concurrent CLI and engine processes) or at `:memory:` for a
+/// throwaway store, so the full cache runs on any platform and in CI. Writes are synchronous.
+#[derive(Clone, Debug)]
+pub struct SqliteCacheDatabase {
+    pub pool: SqlitePool,
+}
+
+impl SqliteCacheDatabase {
+    /// Connects to the SQLite database at `path`, creating the file if it does not exist; passing
+    /// `None` opens a private in-memory store.
+    pub async fn connect(path: Option<String>) -> anyhow::Result<Self> {
+        let options = SqliteConnectOptions::new()
+            .filename(path.as_deref().unwrap_or(":memory:"))
+            .create_if_missing(true);
+        let pool = SqlitePool::connect_with(options).await?;
+        create_tables(&pool).await?;
+        Ok(Self { pool })
+    }
+}
+
+async fn create_tables(pool: &SqlitePool) -> anyhow::Result<()> {
+    // `raw_sql` so all three `CREATE TABLE` statements run; `query()` would compile only the first.
+    sqlx::raw_sql(
+        r"
+        CREATE TABLE IF NOT EXISTS general (
+            id    TEXT PRIMARY KEY,
+            value BLOB NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS currency (
+            code       TEXT PRIMARY KEY,
+            definition TEXT NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS instrument (
+            id         TEXT PRIMARY KEY,
+            definition TEXT NOT NULL
+        );
+        ",
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl CacheDatabase for SqliteCacheDatabase {
+    async fn add(&self, key: String, value: Vec<u8>) -> anyhow::Result<()> {
+        sqlx::query(
+            "INSERT INTO general (id, value) VALUES (?1, ?2) \
+             ON CONFLICT (id) DO UPDATE SET value = ?2",
+        )
+        .bind(key)
+        .bind(value)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn load(&self) -> anyhow::Result<HashMap<String, Vec<u8>>> {
+        let rows = sqlx::query("SELECT id, value FROM general")
+            .fetch_all(&self.pool)
+            .await?;
+        let mut map = HashMap::new();
+        for row in rows {
+            map.insert(row.try_get::<String, _>("id")?, row.try_get::<Vec<u8>, _>("value")?);
+        }
+        Ok(map)
+    }
+
+    async fn add_currency(&self, currency: Currency) -> anyhow::Result<()> {
+        sqlx::query(
+            "INSERT INTO currency (code, definition) VALUES (?1, ?2) \
+             ON CONFLICT (code) DO UPDATE SET definition = ?2",
+        )
+        .bind(currency.code.to_string())
+        .bind(serde_json::to_string(&currency)?)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn load_currency(&self, code: &str) -> anyhow::Result<Option<Currency>> {
+        let row = sqlx::query("SELECT definition FROM currency WHERE code = ?1")
+            .bind(code)
+            .fetch_optional(&self.pool)
+            .await?;
+        match row {
+            Some(row) => Ok(Some(serde_json::from_str(&row.try_get::<String, _>("definition")?)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn load_currencies(&self) -> anyhow::Result<Vec<Currency>> {
+        let rows = sqlx::query("SELECT definition FROM currency ORDER BY code")
+            .fetch_all(&self.pool)
+            .await?;
+        rows.into_iter()
+            .map(|row| Ok(serde_json::from_str(&row.try_get::<String, _>("definition")?)?))
+            .collect()
+    }
+
+    async fn add_instrument(&self, instrument: InstrumentAny) -> anyhow::Result<()> {
+        sqlx::query(
+            "INSERT INTO instrument (id, definition) VALUES (?1, ?2) \
+             ON CONFLICT (id) DO UPDATE SET definition = ?2",
+        )
+        .bind(instrument.id().to_string())
+        .bind(serde_json::to_string(&instrument)?)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn load_instrument(
+        &self,
+        instrument_id: InstrumentId,
+    ) -> anyhow::Result<Option<InstrumentAny>> {
+        let row = sqlx::query("SELECT definition FROM instrument WHERE id = ?1")
+            .bind(instrument_id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+        match row {
+            Some(row) => Ok(Some(serde_json::from_str(&row.try_get::<String, _>("definition")?)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn load_instruments(&self) -> anyhow::Result<Vec<InstrumentAny>> {
+        let rows = sqlx::query("SELECT definition FROM instrument ORDER BY id")
+            .fetch_all(&self.pool)
+            .await?;
+        rows.into_iter()
+            .map(|row| Ok(serde_json::from_str(&row.try_get::<String, _>("definition")?)?))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nautilus_model::{enums::CurrencyType, types::currency::Currency};
+
+    use super::*;
+
+    async fn memory_cache() -> SqliteCacheDatabase {
+        SqliteCacheDatabase::connect(None).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_load_general_objects_when_nothing_in_cache_returns_empty_hashmap() {
+        let cache = memory_cache().await;
+        assert!(cache.load().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_add_general_object_round_trips_synchronously() {
+        let cache = memory_cache().await;
+        let value = String::from("test_value").into_bytes();
+        cache.add(String::from("test_id"), value.clone()).await.unwrap();
+        let result = cache.load().await.unwrap();
+        assert_eq!(result.get("test_id").unwrap().to_owned(), value);
+    }
+
+    #[tokio::test]
+    async fn test_add_and_load_currency() {
+        let cache = memory_cache().await;
+        let btc = Currency::new("BTC", 8, 0, "BTC", CurrencyType::Crypto).unwrap();
+        cache.add_currency(btc).await.unwrap();
+        assert_eq!(cache.load_currency("BTC").await.unwrap().unwrap(), btc);
+        assert_eq!(cache.load_currencies().await.unwrap().len(), 1);
+    }
+}
@@ -51,6 +51,7 @@ pub async fn get_pg_cache_database() -> anyhow::Result<PostgresCacheDatabase> {
         Some(connect_options.username),
         Some(connect_options.password),
         Some(connect_options.database),
+        None,
     )
     .await
     .unwrap())
@@ -59,8 +60,6 @@ pub async fn get_pg_cache_database() -> anyhow::Result<PostgresCacheDatabase> {
 #[cfg(test)]
 #[cfg(target_os = "linux")] // Databases only supported on Linux
 mod tests {
-    use std::time::Duration;
-
     use nautilus_model::{
         enums::CurrencyType,
         identifiers::instrument_id::InstrumentId,
@@ -75,6 +74,8 @@ mod tests {
         types::{currency::Currency, price::Price, quantity::Quantity},
     };
 
+    use nautilus_infrastructure::sql::cache_database::CacheDatabase;
+
     use crate::get_pg_cache_database;
 
     #[tokio::test]
@@ -92,7 +93,7 @@ mod tests {
             .add(String::from("test_id"), test_id_value.clone())
             .await
             .unwrap();
-        tokio::time::sleep(Duration::from_millis(500)).await;
+        pg_cache.flush().await.unwrap();
         let result = pg_cache.load().await.unwrap();
         assert_eq!(result.keys().len(), 1);
         assert_eq!(
@@ -149,7 +150,7 @@ mod tests {
             .add_instrument(InstrumentAny::OptionsContract(options_contract))
             .await
             .unwrap();
-        tokio::time::sleep(Duration::from_secs(2)).await;
+        pg_cache.flush().await.unwrap();
         // Check that currency list is correct
         let currencies = pg_cache.load_currencies().await.unwrap();
         assert_eq!(currencies.len(), 4);